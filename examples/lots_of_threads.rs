@@ -11,6 +11,8 @@ const MAIN_SCRIPT: &str = include_str!("./lua/lots_of_threads.luau");
 
 const ONE_NANOSECOND: Duration = Duration::from_nanos(1);
 
+const NUM_THREADS: usize = 1_000;
+
 pub fn main() -> LuaResult<()> {
     tracing_subscriber::fmt::init();
 
@@ -30,9 +32,11 @@ pub fn main() -> LuaResult<()> {
         })?,
     )?;
 
-    // Load the main script into the runtime
-    let main = lua.load(MAIN_SCRIPT);
-    rt.push_thread_front(main, ())?;
+    // Push a batch of NUM_THREADS copies of the main script at once, instead
+    // of calling push_thread_front in a loop - this is exactly the "lots of
+    // threads at once" workload push_threads_front exists to amortize
+    let threads = (0..NUM_THREADS).map(|_| (lua.load(MAIN_SCRIPT), ()));
+    rt.push_threads_front(threads)?;
 
     // Run until completion
     block_on(rt.run());
@@ -0,0 +1,105 @@
+use mlua::prelude::*;
+
+use crate::runtime::Runtime;
+
+const ERR_ZERO_WORKER_THREADS: &str = "worker_threads must be greater than zero";
+
+/**
+    Builder for a [`Runtime`], allowing for more fine-grained configuration.
+
+    Currently only used to configure the number of worker threads the
+    runtime should use to drive `Send` futures spawned with [`LuaRuntimeExt::spawn`].
+
+    [`LuaRuntimeExt::spawn`]: crate::LuaRuntimeExt::spawn
+*/
+#[derive(Debug, Clone, Copy)]
+pub struct RuntimeBuilder {
+    worker_threads: usize,
+}
+
+impl RuntimeBuilder {
+    /**
+        Creates a new runtime builder, with default settings.
+
+        The default number of worker threads is `1`, meaning that
+        by default a built runtime behaves exactly like one created
+        using [`Runtime::new`].
+    */
+    #[must_use]
+    pub fn new() -> Self {
+        Self { worker_threads: 1 }
+    }
+
+    /**
+        Sets the number of OS threads the runtime will use to drive
+        `Send` futures spawned with [`LuaRuntimeExt::spawn`].
+
+        A value of `1` (the default) keeps the runtime single-threaded -
+        spawned futures are still driven forward, but only on the same
+        thread that calls [`Runtime::run`]. A value greater than `1`
+        spawns `worker_threads - 1` additional OS threads that all run
+        the same shared executor, so that `Send` futures may be stolen
+        and executed across cores.
+
+        Lua scheduling and thread-local futures spawned with
+        [`LuaRuntimeExt::spawn_local`] always stay pinned to the thread
+        that calls [`Runtime::run`], regardless of this setting.
+
+        # Panics
+
+        Panics if `worker_threads` is zero.
+
+        [`LuaRuntimeExt::spawn`]: crate::LuaRuntimeExt::spawn
+        [`LuaRuntimeExt::spawn_local`]: crate::LuaRuntimeExt::spawn_local
+    */
+    #[must_use]
+    pub fn worker_threads(mut self, worker_threads: usize) -> Self {
+        assert!(worker_threads > 0, "{ERR_ZERO_WORKER_THREADS}");
+        self.worker_threads = worker_threads;
+        self
+    }
+
+    /**
+        Builds a new [`Runtime`] for the given Lua state, using this builder's settings.
+
+        # Panics
+
+        Panics if the given Lua state already has a runtime attached to it.
+    */
+    #[must_use]
+    pub fn build(self, lua: &Lua) -> Runtime<'_> {
+        let mut rt = Runtime::new(lua);
+        rt.set_worker_threads(self.worker_threads);
+        rt
+    }
+}
+
+impl Default for RuntimeBuilder {
+    fn default() -> Self {
+        Self::new()
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use mlua::prelude::*;
+
+    use super::RuntimeBuilder;
+    use crate::Runtime;
+
+    #[test]
+    #[should_panic(expected = "worker_threads must be greater than zero")]
+    fn worker_threads_zero_panics() {
+        RuntimeBuilder::new().worker_threads(0);
+    }
+
+    #[test]
+    fn builder_with_multiple_workers_runs_to_completion() {
+        let lua = Lua::new();
+        let rt = Runtime::builder().worker_threads(4).build(&lua);
+        let func = lua.create_function(|_, ()| Ok(())).unwrap();
+        rt.push_thread_front(func, ()).unwrap();
+        futures_lite::future::block_on(rt.run());
+        assert!(rt.status().is_completed());
+    }
+}
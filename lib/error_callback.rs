@@ -0,0 +1,39 @@
+use std::{cell::RefCell, rc::Rc};
+
+use mlua::prelude::*;
+
+type ErrorCallbackFn = dyn Fn(LuaError) + Send + 'static;
+
+/**
+    A cloneable, shareable callback that is called whenever a Lua thread errors.
+*/
+#[derive(Clone)]
+pub struct ThreadErrorCallback {
+    inner: Rc<RefCell<Box<ErrorCallbackFn>>>,
+}
+
+impl ThreadErrorCallback {
+    pub fn new(callback: impl Fn(LuaError) + Send + 'static) -> Self {
+        Self {
+            inner: Rc::new(RefCell::new(Box::new(callback))),
+        }
+    }
+
+    pub fn replace(&self, callback: impl Fn(LuaError) + Send + 'static) {
+        *self.inner.borrow_mut() = Box::new(callback);
+    }
+
+    pub fn clear(&self) {
+        self.replace(|_| {});
+    }
+
+    pub fn call(&self, error: &LuaError) {
+        (self.inner.borrow())(error.clone());
+    }
+}
+
+impl Default for ThreadErrorCallback {
+    fn default() -> Self {
+        Self::new(|e| eprintln!("{e}"))
+    }
+}
@@ -0,0 +1,15 @@
+use mlua::prelude::*;
+
+/**
+    Bundle of Lua functions for interacting with a [`Runtime`] from within Lua.
+
+    Created using [`Runtime::create_functions`].
+
+    [`Runtime`]: crate::Runtime
+    [`Runtime::create_functions`]: crate::Runtime::create_functions
+*/
+#[derive(Debug, Clone)]
+pub struct Functions<'lua> {
+    pub spawn: LuaFunction<'lua>,
+    pub defer: LuaFunction<'lua>,
+}
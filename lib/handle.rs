@@ -0,0 +1,215 @@
+use std::{
+    cell::{Cell, RefCell},
+    error::Error,
+    fmt,
+    future::Future,
+    mem,
+    pin::Pin,
+    rc::Rc,
+    task::{Context, Poll, Waker},
+};
+
+use futures_lite::Stream;
+use mlua::prelude::*;
+use smol::channel::{Receiver, Sender};
+
+/**
+    A handle to a Lua thread that has been pushed onto a [`Runtime`].
+
+    A `Handle` can be awaited to retrieve the final result of the thread,
+    once it finishes running on the runtime. The result is stored as a
+    registry key so that the handle itself does not borrow from the
+    [`Lua`] state - use [`Lua::registry_value`] to turn it back into a
+    [`LuaMultiValue`] once resolved.
+
+    [`Runtime`]: crate::Runtime
+*/
+#[derive(Debug, Clone)]
+pub struct Handle {
+    inner: Rc<RefCell<HandleState>>,
+    cancelled: Rc<Cell<bool>>,
+}
+
+#[derive(Debug)]
+enum HandleState {
+    Pending(Option<Waker>),
+    Done(LuaResult<LuaRegistryKey>),
+    Taken,
+}
+
+/**
+    Error returned by a [`Handle`] (or [`Runtime`](crate::Runtime)) that was
+    cancelled before its backing Lua thread got to run to completion.
+*/
+#[derive(Debug, Clone, Copy)]
+pub struct HandleCancelled;
+
+impl fmt::Display for HandleCancelled {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        write!(f, "thread was cancelled")
+    }
+}
+
+impl Error for HandleCancelled {}
+
+fn cancelled_error() -> LuaError {
+    LuaError::external(HandleCancelled)
+}
+
+impl Handle {
+    pub(crate) fn new() -> Self {
+        Self {
+            inner: Rc::new(RefCell::new(HandleState::Pending(None))),
+            cancelled: Rc::new(Cell::new(false)),
+        }
+    }
+
+    /**
+        Cancels this handle.
+
+        The runtime will not resume the backing thread if it has not
+        already started running, and this handle's awaited result
+        resolves to a distinguished [`HandleCancelled`] error.
+
+        Cancelling a handle whose thread has already completed or
+        errored is a no-op - the original result is kept.
+    */
+    pub fn cancel(&self) {
+        if self.cancelled.replace(true) {
+            return;
+        }
+
+        let mut state = self.inner.borrow_mut();
+        if matches!(&*state, HandleState::Pending(_)) {
+            let waker = match mem::replace(&mut *state, HandleState::Done(Err(cancelled_error())))
+            {
+                HandleState::Pending(waker) => waker,
+                _ => unreachable!(),
+            };
+            drop(state);
+            if let Some(waker) = waker {
+                waker.wake();
+            }
+        }
+    }
+
+    /**
+        Returns `true` if this handle has been cancelled using [`Handle::cancel`].
+    */
+    #[must_use]
+    pub fn is_cancelled(&self) -> bool {
+        self.cancelled.get()
+    }
+
+    /**
+        Completes this handle with the final result of its associated thread.
+
+        Does nothing if the handle was already cancelled using [`Handle::cancel`].
+
+        # Panics
+
+        Panics if the handle has already been completed.
+    */
+    pub(crate) fn complete(&self, result: LuaResult<LuaRegistryKey>) {
+        if self.cancelled.get() {
+            return;
+        }
+
+        let mut state = self.inner.borrow_mut();
+        let previous = mem::replace(&mut *state, HandleState::Done(result));
+        match previous {
+            HandleState::Pending(waker) => {
+                drop(state);
+                if let Some(waker) = waker {
+                    waker.wake();
+                }
+            }
+            HandleState::Done(_) | HandleState::Taken => {
+                panic!("handle was completed more than once")
+            }
+        }
+    }
+}
+
+impl Future for Handle {
+    type Output = LuaResult<LuaRegistryKey>;
+
+    fn poll(self: Pin<&mut Self>, cx: &mut Context<'_>) -> Poll<Self::Output> {
+        let mut state = self.inner.borrow_mut();
+        match &mut *state {
+            HandleState::Pending(waker) => {
+                *waker = Some(cx.waker().clone());
+                Poll::Pending
+            }
+            HandleState::Done(_) => match mem::replace(&mut *state, HandleState::Taken) {
+                HandleState::Done(result) => Poll::Ready(result),
+                _ => unreachable!(),
+            },
+            HandleState::Taken => panic!("handle polled after it already completed"),
+        }
+    }
+}
+
+/**
+    How the runtime should deliver the result(s) of a pushed thread back to its caller.
+*/
+#[derive(Debug, Clone)]
+pub(crate) enum ThreadCompletion {
+    /// Resolve a single [`Handle`] with the thread's terminal result.
+    Value(Handle),
+    /// Forward every genuine `coroutine.yield` as a [`StreamHandle`] item,
+    /// closing the channel once the thread completes or errors.
+    Stream(Sender<LuaResult<LuaRegistryKey>>),
+}
+
+impl ThreadCompletion {
+    /**
+        Resolves this completion with a distinguished [`HandleCancelled`] error,
+        as if the backing thread's [`Handle`] (or [`StreamHandle`]) had been cancelled.
+
+        For a [`Value`](ThreadCompletion::Value) completion this is a no-op if the
+        handle was already resolved. For a [`Stream`](ThreadCompletion::Stream)
+        completion the error is sent as one final item if the receiver is still
+        listening, and silently dropped otherwise.
+    */
+    pub(crate) fn resolve_cancelled(self) {
+        match self {
+            ThreadCompletion::Value(handle) => handle.cancel(),
+            ThreadCompletion::Stream(tx) => {
+                let _ = tx.try_send(Err(cancelled_error()));
+            }
+        }
+    }
+}
+
+/**
+    A handle to a Lua thread that has been pushed onto a [`Runtime`] in
+    streaming mode, that can be polled as a [`Stream`] to retrieve every
+    value the thread yields via `coroutine.yield`, in order.
+
+    Like [`Handle`], yielded values are stored as registry keys - use
+    [`Lua::registry_value`] to turn an item back into a [`LuaMultiValue`].
+
+    The stream closes once the thread completes or errors; a terminal
+    error is delivered as one final `Some(Err(..))` item before closing.
+
+    [`Runtime`]: crate::Runtime
+*/
+#[derive(Debug, Clone)]
+pub struct StreamHandle {
+    receiver: Receiver<LuaResult<LuaRegistryKey>>,
+}
+
+impl StreamHandle {
+    pub(crate) fn new(receiver: Receiver<LuaResult<LuaRegistryKey>>) -> Self {
+        Self { receiver }
+    }
+}
+
+impl Stream for StreamHandle {
+    type Item = LuaResult<LuaRegistryKey>;
+
+    fn poll_next(self: Pin<&mut Self>, cx: &mut Context<'_>) -> Poll<Option<Self::Item>> {
+        Pin::new(&mut self.get_mut().receiver).poll_next(cx)
+    }
+}
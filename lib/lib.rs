@@ -1,3 +1,4 @@
+mod builder;
 mod error_callback;
 mod functions;
 mod handle;
@@ -7,8 +8,9 @@ mod status;
 mod traits;
 mod util;
 
+pub use builder::RuntimeBuilder;
 pub use functions::Functions;
-pub use handle::Handle;
+pub use handle::{Handle, HandleCancelled, StreamHandle};
 pub use runtime::Runtime;
 pub use status::Status;
 pub use traits::{IntoLuaThread, LuaRuntimeExt};
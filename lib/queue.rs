@@ -1,10 +1,20 @@
-use std::sync::Arc;
+use std::{
+    cell::RefCell,
+    collections::VecDeque,
+    future::Future,
+    pin::Pin,
+    rc::Rc,
+    sync::Arc,
+};
 
 use concurrent_queue::ConcurrentQueue;
 use mlua::prelude::*;
 use smol::channel::{unbounded, Receiver, Sender};
 
-use crate::IntoLuaThread;
+use crate::{
+    handle::{Handle, StreamHandle, ThreadCompletion},
+    traits::IntoLuaThread,
+};
 
 const ERR_OOM: &str = "out of memory";
 
@@ -16,11 +26,21 @@ const ERR_OOM: &str = "out of memory";
 */
 #[derive(Debug, Clone)]
 pub struct ThreadQueue {
-    queue: Arc<ConcurrentQueue<ThreadWithArgs>>,
+    queue: Arc<ConcurrentQueue<(ThreadWithArgs, ThreadCompletion)>>,
     signal_tx: Sender<()>,
     signal_rx: Receiver<()>,
 }
 
+/**
+    Queue used for spawned Lua threads - threads pushed to the front of the runtime.
+*/
+pub type SpawnedThreadQueue = ThreadQueue;
+
+/**
+    Queue used for deferred Lua threads - threads pushed to the back of the runtime.
+*/
+pub type DeferredThreadQueue = ThreadQueue;
+
 impl ThreadQueue {
     pub fn new() -> Self {
         let queue = Arc::new(ConcurrentQueue::unbounded());
@@ -32,33 +52,121 @@ impl ThreadQueue {
         }
     }
 
-    pub fn push<'lua>(
+    /**
+        Pushes a single item onto the queue, signalling the queue listener once.
+    */
+    pub fn push_item<'lua>(
         &self,
         lua: &'lua Lua,
         thread: impl IntoLuaThread<'lua>,
         args: impl IntoLuaMulti<'lua>,
     ) -> LuaResult<()> {
+        self.push_item_with_handle(lua, thread, args)?;
+        Ok(())
+    }
+
+    /**
+        Pushes a single item onto the queue, signalling the queue listener once,
+        and returns a [`Handle`] that can be used to retrieve its result.
+    */
+    pub fn push_item_with_handle<'lua>(
+        &self,
+        lua: &'lua Lua,
+        thread: impl IntoLuaThread<'lua>,
+        args: impl IntoLuaMulti<'lua>,
+    ) -> LuaResult<Handle> {
         let thread = thread.into_lua_thread(lua)?;
         let args = args.into_lua_multi(lua)?;
         let stored = ThreadWithArgs::new(lua, thread, args);
+        let handle = Handle::new();
 
-        self.queue.push(stored).unwrap();
+        self.queue
+            .push((stored, ThreadCompletion::Value(handle.clone())))
+            .unwrap();
         self.signal_tx.try_send(()).unwrap();
 
-        Ok(())
+        Ok(handle)
     }
 
-    pub fn drain<'outer, 'lua>(
+    /**
+        Pushes a single item onto the queue in streaming mode, signalling
+        the queue listener once, and returns a [`StreamHandle`] that
+        yields every value the thread yields via `coroutine.yield`.
+    */
+    pub fn push_item_streaming<'lua>(
+        &self,
+        lua: &'lua Lua,
+        thread: impl IntoLuaThread<'lua>,
+        args: impl IntoLuaMulti<'lua>,
+    ) -> LuaResult<StreamHandle> {
+        let thread = thread.into_lua_thread(lua)?;
+        let args = args.into_lua_multi(lua)?;
+        let stored = ThreadWithArgs::new(lua, thread, args);
+        let (tx, rx) = unbounded();
+
+        self.queue.push((stored, ThreadCompletion::Stream(tx))).unwrap();
+        self.signal_tx.try_send(()).unwrap();
+
+        Ok(StreamHandle::new(rx))
+    }
+
+    /**
+        Pushes a batch of items onto the queue, signalling the queue
+        listener exactly once for the whole batch (an empty batch
+        sends no signal at all).
+
+        Mirrors [`async-executor`]'s `spawn_batch`, amortizing the cost
+        of waking the listener across the whole batch instead of paying
+        it once per item. Each item is still pushed onto the underlying
+        queue individually.
+
+        [`async-executor`]: https://docs.rs/async-executor
+    */
+    pub fn push_batch<'lua>(
+        &self,
+        lua: &'lua Lua,
+        items: impl IntoIterator<Item = (impl IntoLuaThread<'lua>, impl IntoLuaMulti<'lua>)>,
+    ) -> LuaResult<Vec<Handle>> {
+        // Convert every item in the batch before pushing anything onto the
+        // queue - if a conversion partway through the batch errors, nothing
+        // has been made visible to the queue listener yet, so we can bail
+        // out with `?` without orphaning earlier items in the batch.
+        let mut prepared = Vec::new();
+        for (thread, args) in items {
+            let thread = thread.into_lua_thread(lua)?;
+            let args = args.into_lua_multi(lua)?;
+            prepared.push((ThreadWithArgs::new(lua, thread, args), Handle::new()));
+        }
+
+        let mut handles = Vec::with_capacity(prepared.len());
+        for (stored, handle) in prepared {
+            self.queue
+                .push((stored, ThreadCompletion::Value(handle.clone())))
+                .unwrap();
+            handles.push(handle);
+        }
+
+        if !handles.is_empty() {
+            self.signal_tx.try_send(()).unwrap();
+        }
+
+        Ok(handles)
+    }
+
+    pub fn drain_items<'outer, 'lua>(
         &'outer self,
         lua: &'lua Lua,
-    ) -> impl Iterator<Item = (LuaThread<'lua>, LuaMultiValue<'lua>)> + 'outer
+    ) -> impl Iterator<Item = (LuaThread<'lua>, LuaMultiValue<'lua>, ThreadCompletion)> + 'outer
     where
         'lua: 'outer,
     {
-        self.queue.try_iter().map(|stored| stored.into_inner(lua))
+        self.queue.try_iter().map(|(stored, completion)| {
+            let (thread, args) = stored.into_inner(lua);
+            (thread, args, completion)
+        })
     }
 
-    pub async fn listen(&self) {
+    pub async fn wait_for_item(&self) {
         self.signal_rx.recv().await.unwrap();
         // Drain any pending receives
         loop {
@@ -104,3 +212,92 @@ impl ThreadWithArgs {
         (thread, args)
     }
 }
+
+/**
+    Queue for storing thread-local futures that should be spawned
+    onto the local executor, along with a way to listen for new
+    futures being pushed onto the queue.
+*/
+#[derive(Clone)]
+pub struct FuturesQueue {
+    queue: Rc<RefCell<VecDeque<Pin<Box<dyn Future<Output = ()> + 'static>>>>>,
+    signal_tx: Sender<()>,
+    signal_rx: Receiver<()>,
+}
+
+impl FuturesQueue {
+    pub fn new() -> Self {
+        let queue = Rc::new(RefCell::new(VecDeque::new()));
+        let (signal_tx, signal_rx) = unbounded();
+        Self {
+            queue,
+            signal_tx,
+            signal_rx,
+        }
+    }
+
+    pub fn push_item(&self, fut: impl Future<Output = ()> + 'static) {
+        self.queue.borrow_mut().push_back(Box::pin(fut));
+        self.signal_tx.try_send(()).unwrap();
+    }
+
+    pub fn drain_items(&self) -> impl Iterator<Item = Pin<Box<dyn Future<Output = ()> + 'static>>> {
+        self.queue.borrow_mut().drain(..).collect::<Vec<_>>().into_iter()
+    }
+
+    pub async fn wait_for_item(&self) {
+        self.signal_rx.recv().await.unwrap();
+        // Drain any pending receives
+        loop {
+            match self.signal_rx.try_recv() {
+                Ok(_) => continue,
+                Err(_) => break,
+            }
+        }
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use mlua::prelude::*;
+
+    use super::ThreadQueue;
+
+    /// Args type that converts successfully for `Ok` and errors for `Fail`,
+    /// used to simulate a conversion failure partway through a `push_batch` call.
+    enum TestArgs {
+        Ok,
+        Fail,
+    }
+
+    impl<'lua> IntoLuaMulti<'lua> for TestArgs {
+        fn into_lua_multi(self, lua: &'lua Lua) -> LuaResult<LuaMultiValue<'lua>> {
+            match self {
+                TestArgs::Ok => ().into_lua_multi(lua),
+                TestArgs::Fail => Err(LuaError::RuntimeError("boom".to_string())),
+            }
+        }
+    }
+
+    #[test]
+    fn push_batch_does_not_orphan_earlier_items_on_conversion_failure() {
+        let lua = Lua::new();
+        let queue = ThreadQueue::new();
+        let func = lua.create_function(|_, ()| Ok(())).unwrap();
+
+        let result = queue.push_batch(
+            &lua,
+            vec![
+                (func.clone(), TestArgs::Ok),
+                (func.clone(), TestArgs::Ok),
+                (func, TestArgs::Fail),
+            ],
+        );
+        assert!(result.is_err());
+
+        // Nothing from the failed batch should have reached the queue -
+        // otherwise these items would sit there forever with nobody left
+        // to signal and drain them.
+        assert_eq!(queue.drain_items(&lua).count(), 0);
+    }
+}
@@ -1,24 +1,28 @@
 #![allow(clippy::module_name_repetitions)]
 
 use std::{
-    cell::Cell,
+    cell::{Cell, RefCell},
     rc::{Rc, Weak as WeakRc},
     sync::{Arc, Weak as WeakArc},
+    thread,
 };
 
 use futures_lite::prelude::*;
 use mlua::prelude::*;
 
-use async_executor::{Executor, LocalExecutor};
+use async_executor::{Executor, LocalExecutor, StaticExecutor};
+use smol::channel::unbounded;
 use tracing::Instrument;
 
 use crate::{
+    builder::RuntimeBuilder,
     error_callback::ThreadErrorCallback,
-    handle::Handle,
+    functions::Functions,
+    handle::{Handle, StreamHandle, ThreadCompletion},
     queue::{DeferredThreadQueue, FuturesQueue, SpawnedThreadQueue},
     status::Status,
     traits::IntoLuaThread,
-    util::run_until_yield,
+    util::{run_thread_streaming, run_until_yield},
 };
 
 const ERR_METADATA_ALREADY_ATTACHED: &str = "\
@@ -36,6 +40,8 @@ const ERR_SET_CALLBACK_WHEN_RUNNING: &str = "\
 Cannot set error callback when runtime is running!\
 ";
 
+const ERR_OOM: &str = "out of memory";
+
 /**
     A runtime for running Lua threads and async tasks.
 */
@@ -46,6 +52,8 @@ pub struct Runtime<'lua> {
     queue_defer: DeferredThreadQueue,
     error_callback: ThreadErrorCallback,
     status: Rc<Cell<Status>>,
+    cancelled: Rc<Cell<bool>>,
+    worker_threads: usize,
 }
 
 impl<'lua> Runtime<'lua> {
@@ -88,9 +96,23 @@ impl<'lua> Runtime<'lua> {
             queue_defer,
             error_callback,
             status,
+            cancelled: Rc::new(Cell::new(false)),
+            worker_threads: 1,
         }
     }
 
+    /**
+        Creates a new [`RuntimeBuilder`] for configuring a runtime before creation.
+    */
+    #[must_use]
+    pub fn builder() -> RuntimeBuilder {
+        RuntimeBuilder::new()
+    }
+
+    pub(crate) fn set_worker_threads(&mut self, worker_threads: usize) {
+        self.worker_threads = worker_threads;
+    }
+
     /**
         Returns the current status of this runtime.
     */
@@ -99,6 +121,29 @@ impl<'lua> Runtime<'lua> {
         self.status.get()
     }
 
+    /**
+        Cancels this runtime.
+
+        Once cancelled, the next time the main loop inside [`Runtime::run`]
+        (or [`Runtime::run_leaked`]) wakes up, it will stop draining new
+        threads from the spawn/defer queues, drop any outstanding tasks,
+        and return with [`Runtime::status`] set to [`Status::Cancelled`].
+
+        This is idempotent and may be called before the runtime has started,
+        in which case it will exit immediately once run.
+    */
+    pub fn cancel(&self) {
+        self.cancelled.set(true);
+    }
+
+    /**
+        Returns `true` if this runtime has been cancelled using [`Runtime::cancel`].
+    */
+    #[must_use]
+    pub fn is_cancelled(&self) -> bool {
+        self.cancelled.get()
+    }
+
     /**
         Sets the error callback for this runtime.
 
@@ -160,6 +205,66 @@ impl<'lua> Runtime<'lua> {
             .push_item_with_handle(self.lua, thread, args)
     }
 
+    /**
+        Spawns a batch of chunks / functions / threads onto the front of the runtime queue.
+
+        Equivalent to calling [`Runtime::push_thread_front`] once per item, but
+        amortizes the cost of signalling the runtime's main loop across the
+        whole batch instead of paying it once per thread - this matters a lot
+        when enqueuing a very large number of threads at once.
+
+        # Returns
+
+        Returns one [`Handle`] per pushed thread, in the same order as `items`.
+
+        # Errors
+
+        Errors when out of memory.
+    */
+    pub fn push_threads_front(
+        &self,
+        items: impl IntoIterator<
+            Item = (
+                impl IntoLuaThread<'lua>,
+                impl IntoLuaMulti<'lua>,
+            ),
+        >,
+    ) -> LuaResult<Vec<Handle>> {
+        tracing::debug!(deferred = false, "new runtime thread batch");
+        self.queue_spawn.push_batch(self.lua, items)
+    }
+
+    /**
+        Spawns a chunk / function / thread onto the front of the runtime queue in streaming mode.
+
+        Unlike [`Runtime::push_thread_front`], which only ever resolves with the
+        thread's terminal result, this repeatedly resumes the thread and forwards
+        every value yielded via `coroutine.yield` to the returned [`StreamHandle`],
+        which may be polled as a [`Stream`](futures_lite::Stream) of `LuaResult`s.
+
+        Like [`Handle`], each item is stored as a registry key rather than a
+        [`LuaMultiValue`] directly, so that [`StreamHandle`] does not borrow
+        from the given [`Lua`] state - use [`Lua::registry_value`] to turn an
+        item back into a `LuaMultiValue` as it's received.
+
+        # Returns
+
+        Returns a [`StreamHandle`] that can be used to retrieve every value
+        the thread yields, in order, until it completes or errors.
+
+        # Errors
+
+        Errors when out of memory.
+    */
+    pub fn push_thread_front_streaming(
+        &self,
+        thread: impl IntoLuaThread<'lua>,
+        args: impl IntoLuaMulti<'lua>,
+    ) -> LuaResult<StreamHandle> {
+        tracing::debug!(deferred = false, streaming = true, "new runtime thread");
+        self.queue_spawn.push_item_streaming(self.lua, thread, args)
+    }
+
     /**
         Defers a chunk / function / thread onto the runtime queue.
 
@@ -187,6 +292,259 @@ impl<'lua> Runtime<'lua> {
             .push_item_with_handle(self.lua, thread, args)
     }
 
+    /**
+        Defers a batch of chunks / functions / threads onto the back of the runtime queue.
+
+        See [`Runtime::push_threads_front`] for more information.
+
+        # Errors
+
+        Errors when out of memory.
+    */
+    pub fn push_threads_back(
+        &self,
+        items: impl IntoIterator<
+            Item = (
+                impl IntoLuaThread<'lua>,
+                impl IntoLuaMulti<'lua>,
+            ),
+        >,
+    ) -> LuaResult<Vec<Handle>> {
+        tracing::debug!(deferred = true, "new runtime thread batch");
+        self.queue_defer.push_batch(self.lua, items)
+    }
+
+    /**
+        Creates a table of Lua functions for interacting with this runtime from within Lua.
+
+        # Errors
+
+        Errors when out of memory.
+    */
+    pub fn create_functions(&self) -> LuaResult<Functions<'lua>> {
+        let rt_spawn = self.clone();
+        let spawn = self.lua.create_function(
+            move |lua, (value, args): (LuaValue, LuaMultiValue)| {
+                let thread = match value {
+                    LuaValue::Thread(t) => t,
+                    LuaValue::Function(f) => lua.create_thread(f)?,
+                    _ => {
+                        return Err(LuaError::RuntimeError(
+                            "spawn expects a thread or function".to_string(),
+                        ))
+                    }
+                };
+                rt_spawn.push_thread_front(thread, args)?;
+                Ok(())
+            },
+        )?;
+
+        let rt_defer = self.clone();
+        let defer = self.lua.create_function(
+            move |lua, (value, args): (LuaValue, LuaMultiValue)| {
+                let thread = match value {
+                    LuaValue::Thread(t) => t,
+                    LuaValue::Function(f) => lua.create_thread(f)?,
+                    _ => {
+                        return Err(LuaError::RuntimeError(
+                            "defer expects a thread or function".to_string(),
+                        ))
+                    }
+                };
+                rt_defer.push_thread_back(thread, args)?;
+                Ok(())
+            },
+        )?;
+
+        Ok(Functions { spawn, defer })
+    }
+
+    /**
+        Spawns the `worker_threads - 1` additional OS worker threads configured via
+        [`RuntimeBuilder::worker_threads`], each running `spawn_one` with its index
+        and a clone of `stop_rx`, returning their join handles.
+
+        Shared between [`Runtime::run`] and [`Runtime::run_leaked`], which differ
+        only in what kind of executor handle `spawn_one` captures.
+
+        [`RuntimeBuilder::worker_threads`]: crate::RuntimeBuilder::worker_threads
+    */
+    fn spawn_worker_threads(
+        worker_threads: usize,
+        stop_rx: &smol::channel::Receiver<()>,
+        spawn_one: impl Fn(usize, smol::channel::Receiver<()>) -> thread::JoinHandle<()>,
+    ) -> Vec<thread::JoinHandle<()>> {
+        (1..worker_threads)
+            .map(|idx| spawn_one(idx, stop_rx.clone()))
+            .collect()
+    }
+
+    /**
+        Drives `local_exec` and the spawn/defer/futures queues forward until
+        every pushed Lua thread has completed or the runtime is cancelled.
+
+        Shared between [`Runtime::run`] and [`Runtime::run_leaked`] - the only
+        difference between the two is which kind of executor (ref-counted or
+        leaked/`'static`) drives this future, and how many OS worker threads
+        help it along.
+
+        On cancellation, resolves every [`Handle`]/[`StreamHandle`] still
+        outstanding - whether it was still sitting in a queue, or already
+        dispatched to `local_exec` - with a cancelled error, so that awaiting
+        one can never hang forever.
+    */
+    #[allow(clippy::too_many_lines)]
+    async fn main_loop(&self, local_exec: &LocalExecutor<'lua>, fut_queue: &FuturesQueue) {
+        // Completions already dispatched to `local_exec`, paired with a
+        // shared flag the dispatched task flips once it finishes normally,
+        // so that a cancellation can resolve whatever is left outstanding.
+        let in_flight: RefCell<Vec<(ThreadCompletion, Rc<Cell<bool>>)>> = RefCell::new(Vec::new());
+
+        let process_thread = |thread: LuaThread<'lua>, args, completion: ThreadCompletion| {
+            // NOTE: Thread may have been cancelled from Lua (or via
+            // Handle::cancel) before we got here, so we need to check it again
+            if let ThreadCompletion::Value(handle) = &completion {
+                if handle.is_cancelled() {
+                    return;
+                }
+            }
+            if thread.status() != LuaThreadStatus::Resumable {
+                return;
+            }
+
+            let done = Rc::new(Cell::new(false));
+            in_flight
+                .borrow_mut()
+                .push((completion.clone(), Rc::clone(&done)));
+
+            match completion {
+                ThreadCompletion::Value(handle) => {
+                    local_exec
+                        .spawn(async move {
+                            let result = run_until_yield(thread, args).await;
+                            match result {
+                                Ok(values) => {
+                                    let key = self
+                                        .lua
+                                        .create_registry_value(values.into_vec())
+                                        .expect(ERR_OOM);
+                                    handle.complete(Ok(key));
+                                }
+                                Err(e) => {
+                                    self.error_callback.call(&e);
+                                    handle.complete(Err(e));
+                                }
+                            }
+                            done.set(true);
+                        })
+                        .detach();
+                }
+                ThreadCompletion::Stream(tx) => {
+                    local_exec
+                        .spawn(async move {
+                            run_thread_streaming(self.lua, thread, args, tx).await;
+                            done.set(true);
+                        })
+                        .detach();
+                }
+            }
+        };
+
+        loop {
+            // A cancellation observed here means we stop draining the queues
+            // for new work - anything still queued or in flight is resolved
+            // with a cancelled error once we break out below.
+            if self.cancelled.get() {
+                tracing::debug!("runtime cancelled, exiting main loop");
+                break;
+            }
+
+            let fut_spawn = self.queue_spawn.wait_for_item(); // 1
+            let fut_defer = self.queue_defer.wait_for_item(); // 2
+            let fut_futs = fut_queue.wait_for_item(); // 3
+
+            // 4
+            let mut num_processed = 0;
+            let span_tick = tracing::debug_span!("tick_executor");
+            let fut_tick = async {
+                local_exec.tick().await;
+                // NOTE: Try to do as much work as possible instead of just a single tick()
+                num_processed += 1;
+                while local_exec.try_tick() {
+                    num_processed += 1;
+                }
+            };
+
+            // 1 + 2 + 3 + 4
+            fut_spawn
+                .or(fut_defer)
+                .or(fut_futs)
+                .or(fut_tick.instrument(span_tick.or_current()))
+                .await;
+
+            if self.cancelled.get() {
+                tracing::debug!("runtime cancelled, exiting main loop");
+                break;
+            }
+
+            // Emit traces
+            if num_processed > 0 {
+                tracing::trace!(num_processed, "tasks_processed");
+            }
+
+            // Process spawned threads first, then deferred threads
+            let mut num_spawned = 0;
+            let mut num_deferred = 0;
+            for (thread, args, completion) in self.queue_spawn.drain_items(self.lua) {
+                process_thread(thread, args, completion);
+                num_spawned += 1;
+            }
+            for (thread, args, completion) in self.queue_defer.drain_items(self.lua) {
+                process_thread(thread, args, completion);
+                num_deferred += 1;
+            }
+            if num_spawned > 0 || num_deferred > 0 {
+                tracing::trace!(num_spawned, num_deferred, "tasks_spawned");
+            }
+
+            // Process spawned futures
+            let mut num_futs = 0;
+            for fut in fut_queue.drain_items() {
+                local_exec.spawn(fut).detach();
+                num_futs += 1;
+            }
+            if num_futs > 0 {
+                tracing::trace!(num_futs, "futures_spawned");
+            }
+
+            // Drop every in-flight entry that finished normally, so a later
+            // cancellation only ever resolves what is genuinely still running
+            in_flight.borrow_mut().retain(|(_, done)| !done.get());
+
+            // Empty executor = we didn't spawn any new Lua tasks
+            // above, and there are no remaining tasks to run later
+            if local_exec.is_empty() {
+                break;
+            }
+        }
+
+        for (_, _, completion) in self.queue_spawn.drain_items(self.lua) {
+            completion.resolve_cancelled();
+        }
+        for (_, _, completion) in self.queue_defer.drain_items(self.lua) {
+            completion.resolve_cancelled();
+        }
+        for (completion, done) in in_flight.into_inner() {
+            // A task may have finished normally (and already sent its real
+            // terminal item/result) in the same tick the cancellation was
+            // observed, before the per-iteration retain() above ran - don't
+            // clobber it with a spurious cancelled error.
+            if !done.get() {
+                completion.resolve_cancelled();
+            }
+        }
+    }
+
     /**
         Runs the runtime until all Lua threads have completed.
 
@@ -244,92 +602,49 @@ impl<'lua> Runtime<'lua> {
             This ordering is vital to ensure that we don't accidentally exit the main loop
             when there are new Lua threads to enqueue and potentially more work to be done.
         */
-        let fut = async {
-            let process_thread = |thread: LuaThread<'lua>, args| {
-                // NOTE: Thread may have been cancelled from Lua
-                // before we got here, so we need to check it again
-                if thread.status() == LuaThreadStatus::Resumable {
-                    local_exec
-                        .spawn(async move {
-                            if let Err(e) = run_until_yield(thread, args).await {
-                                self.error_callback.call(&e);
-                            }
-                        })
-                        .detach();
-                }
-            };
-
-            loop {
-                let fut_spawn = self.queue_spawn.wait_for_item(); // 1
-                let fut_defer = self.queue_defer.wait_for_item(); // 2
-                let fut_futs = fut_queue.wait_for_item(); // 3
-
-                // 4
-                let mut num_processed = 0;
-                let span_tick = tracing::debug_span!("tick_executor");
-                let fut_tick = async {
-                    local_exec.tick().await;
-                    // NOTE: Try to do as much work as possible instead of just a single tick()
-                    num_processed += 1;
-                    while local_exec.try_tick() {
-                        num_processed += 1;
-                    }
-                };
+        let fut = self.main_loop(&local_exec, &fut_queue);
 
-                // 1 + 2 + 3 + 4
-                fut_spawn
-                    .or(fut_defer)
-                    .or(fut_futs)
-                    .or(fut_tick.instrument(span_tick.or_current()))
-                    .await;
-
-                // Emit traces
-                if num_processed > 0 {
-                    tracing::trace!(num_processed, "tasks_processed");
-                }
-
-                // Process spawned threads first, then deferred threads
-                let mut num_spawned = 0;
-                let mut num_deferred = 0;
-                for (thread, args) in self.queue_spawn.drain_items(self.lua) {
-                    process_thread(thread, args);
-                    num_spawned += 1;
-                }
-                for (thread, args) in self.queue_defer.drain_items(self.lua) {
-                    process_thread(thread, args);
-                    num_deferred += 1;
-                }
-                if num_spawned > 0 || num_deferred > 0 {
-                    tracing::trace!(num_spawned, num_deferred, "tasks_spawned");
-                }
-
-                // Process spawned futures
-                let mut num_futs = 0;
-                for fut in fut_queue.drain_items() {
-                    local_exec.spawn(fut).detach();
-                    num_futs += 1;
-                }
-                if num_futs > 0 {
-                    tracing::trace!(num_futs, "futures_spawned");
-                }
+        /*
+            Spawn additional OS worker threads that drive the same shared
+            main executor, so that `Send` futures spawned with
+            `LuaRuntimeExt::spawn` can be stolen and run across cores.
 
-                // Empty executor = we didn't spawn any new Lua tasks
-                // above, and there are no remaining tasks to run later
-                if local_exec.is_empty() {
-                    break;
-                }
-            }
-        };
+            Lua scheduling itself never runs on these threads - it is only
+            ever driven by `local_exec`, which stays pinned to this thread.
+        */
+        let (stop_tx, stop_rx) = unbounded::<()>();
+        let workers = Self::spawn_worker_threads(self.worker_threads, &stop_rx, |idx, stop_rx| {
+            let exec = Arc::clone(&main_exec);
+            thread::Builder::new()
+                .name(format!("mlua-luau-runtime-worker-{idx}"))
+                .spawn(move || {
+                    futures_lite::future::block_on(exec.run(async move {
+                        stop_rx.recv().await.ok();
+                    }));
+                })
+                .expect("failed to spawn runtime worker thread")
+        });
 
         // Run the executor inside a span until all lua threads complete
         self.status.set(Status::Running);
-        tracing::debug!("starting runtime");
+        tracing::debug!(worker_threads = self.worker_threads, "starting runtime");
 
         let span = tracing::debug_span!("run_executor");
         main_exec.run(fut).instrument(span.or_current()).await;
 
-        tracing::debug!("runtime completed");
-        self.status.set(Status::Completed);
+        // Signal worker threads to stop and wait for them to exit
+        drop(stop_tx);
+        for worker in workers {
+            worker.join().expect("runtime worker thread panicked");
+        }
+
+        if self.cancelled.get() {
+            tracing::debug!("runtime cancelled");
+            self.status.set(Status::Cancelled);
+        } else {
+            tracing::debug!("runtime completed");
+            self.status.set(Status::Completed);
+        }
 
         // Clean up
         self.lua
@@ -339,6 +654,97 @@ impl<'lua> Runtime<'lua> {
             .remove_app_data::<WeakRc<FuturesQueue>>()
             .expect(ERR_METADATA_REMOVED);
     }
+
+    /**
+        Runs the runtime until all Lua threads have completed, using a leaked,
+        never-dropped main executor and futures queue instead of the ref-counted
+        ones used by [`Runtime::run`]. The thread-local executor that drives Lua
+        threads is not leaked - it is created anew and dropped on every call.
+
+        This is an opt-in mode for embedders that create a single [`Runtime`]
+        and keep it running for the entire lifetime of the process - a game
+        host, a long-lived server - where the per-spawn bookkeeping that the
+        ref-counted executors pay for (so that they can be torn down cleanly)
+        is pure overhead that's never actually used. Leaking the executors
+        once, up front, lets [`LuaRuntimeExt::spawn`] store a `'static` handle
+        in Lua app-data instead of a [`Weak`] that must be upgraded on every
+        single spawn, which removes the `upgrade().expect(..)` check from
+        that hot path.
+
+        Note that the leaked executor and queue are never dropped - this trades a
+        one-time memory leak, bounded by the lifetime of the process, for
+        lower per-task overhead. Do not use this if the runtime is expected
+        to be created and torn down repeatedly.
+
+        [`Weak`]: std::sync::Weak
+        [`LuaRuntimeExt::spawn`]: crate::LuaRuntimeExt::spawn
+
+        # Panics
+
+        Panics if the given Lua state already has a runtime attached to it.
+    */
+    pub async fn run_leaked(&self) {
+        let local_exec = LocalExecutor::new();
+        let main_exec: &'static StaticExecutor = Executor::new().leak();
+        let fut_queue: &'static FuturesQueue = Box::leak(Box::new(FuturesQueue::new()));
+
+        assert!(
+            self.lua.app_data_ref::<&'static StaticExecutor>().is_none(),
+            "{ERR_METADATA_ALREADY_ATTACHED}"
+        );
+        assert!(
+            self.lua
+                .app_data_ref::<&'static FuturesQueue>()
+                .is_none(),
+            "{ERR_METADATA_ALREADY_ATTACHED}"
+        );
+
+        self.lua.set_app_data(main_exec);
+        self.lua.set_app_data(fut_queue);
+
+        let fut = self.main_loop(&local_exec, fut_queue);
+
+        // Leaked runtimes get the same worker-thread support as `Runtime::run` -
+        // `main_exec` is already `&'static`, so worker threads can just copy the
+        // reference directly instead of cloning an `Arc`.
+        let (stop_tx, stop_rx) = unbounded::<()>();
+        let workers = Self::spawn_worker_threads(self.worker_threads, &stop_rx, |idx, stop_rx| {
+            thread::Builder::new()
+                .name(format!("mlua-luau-runtime-worker-{idx}"))
+                .spawn(move || {
+                    futures_lite::future::block_on(main_exec.run(async move {
+                        stop_rx.recv().await.ok();
+                    }));
+                })
+                .expect("failed to spawn runtime worker thread")
+        });
+
+        self.status.set(Status::Running);
+        tracing::debug!(worker_threads = self.worker_threads, "starting leaked runtime");
+
+        main_exec.run(fut).await;
+
+        // Signal worker threads to stop and wait for them to exit
+        drop(stop_tx);
+        for worker in workers {
+            worker.join().expect("runtime worker thread panicked");
+        }
+
+        if self.cancelled.get() {
+            tracing::debug!("leaked runtime cancelled");
+            self.status.set(Status::Cancelled);
+        } else {
+            tracing::debug!("leaked runtime completed");
+            self.status.set(Status::Completed);
+        }
+
+        self.lua
+            .remove_app_data::<&'static StaticExecutor>()
+            .expect(ERR_METADATA_REMOVED);
+        self.lua
+            .remove_app_data::<&'static FuturesQueue>()
+            .expect(ERR_METADATA_REMOVED);
+    }
 }
 
 impl Drop for Runtime<'_> {
@@ -354,3 +760,98 @@ impl Drop for Runtime<'_> {
             .expect(ERR_METADATA_REMOVED);
     }
 }
+
+#[cfg(test)]
+mod tests {
+    use mlua::prelude::*;
+
+    use super::Runtime;
+    use crate::handle::StreamHandle;
+
+    /// Drains a [`StreamHandle`] of `i64` items, for tests that assert on
+    /// exactly which values were forwarded as genuine `coroutine.yield`s.
+    fn drain_stream_values(lua: &Lua, mut stream: StreamHandle) -> Vec<i64> {
+        use futures_lite::StreamExt;
+
+        futures_lite::future::block_on(async {
+            let mut out = Vec::new();
+            while let Some(item) = stream.next().await {
+                let argsv = lua.registry_value::<Vec<LuaValue>>(&item.unwrap()).unwrap();
+                let multi = LuaMultiValue::from_vec(argsv);
+                let (value,): (i64,) = FromLuaMulti::from_lua_multi(multi, lua).unwrap();
+                out.push(value);
+            }
+            out
+        })
+    }
+
+    #[test]
+    fn run_leaked_completes_with_worker_threads() {
+        let lua = Lua::new();
+        let rt = Runtime::builder().worker_threads(2).build(&lua);
+        let func = lua.create_function(|_, ()| Ok(())).unwrap();
+        rt.push_thread_front(func, ()).unwrap();
+        futures_lite::future::block_on(rt.run_leaked());
+        assert!(rt.status().is_completed());
+    }
+
+    #[test]
+    fn streaming_handle_yields_each_value_in_order() {
+        let lua = Lua::new();
+        let rt = Runtime::new(&lua);
+        let thread = lua
+            .create_thread(
+                lua.load("for i = 1, 3 do coroutine.yield(i) end")
+                    .into_function()
+                    .unwrap(),
+            )
+            .unwrap();
+        let stream = rt.push_thread_front_streaming(thread, ()).unwrap();
+        futures_lite::future::block_on(rt.run());
+
+        assert_eq!(drain_stream_values(&lua, stream), vec![1, 2, 3]);
+    }
+
+    #[test]
+    fn streaming_handle_skips_internal_async_polls() {
+        let lua = Lua::new();
+        let rt = Runtime::new(&lua);
+
+        // A no-op async function - awaiting it drives an internal async
+        // poll via LuaThread::into_async, which must not be mistaken for
+        // a genuine `coroutine.yield` and forwarded as a stream item.
+        let wait = lua
+            .create_async_function(|_, ()| async move { Ok(()) })
+            .unwrap();
+        lua.globals().set("wait", wait).unwrap();
+
+        let thread = lua
+            .create_thread(
+                lua.load("coroutine.yield(1) wait() coroutine.yield(2)")
+                    .into_function()
+                    .unwrap(),
+            )
+            .unwrap();
+        let stream = rt.push_thread_front_streaming(thread, ()).unwrap();
+        futures_lite::future::block_on(rt.run());
+
+        assert_eq!(drain_stream_values(&lua, stream), vec![1, 2]);
+    }
+
+    #[test]
+    fn cancel_before_run_resolves_all_handles() {
+        let lua = Lua::new();
+        let rt = Runtime::new(&lua);
+        let func = lua.create_function(|_, ()| Ok(())).unwrap();
+        let handle = rt.push_thread_front(func, ()).unwrap();
+
+        rt.cancel();
+        futures_lite::future::block_on(rt.run());
+
+        let result = futures_lite::future::block_on(handle);
+        assert!(
+            result.is_err(),
+            "handle must resolve (with a cancellation error) instead of hanging forever"
+        );
+    }
+}
@@ -0,0 +1,40 @@
+/**
+    Status of a [`Runtime`].
+
+    [`Runtime`]: crate::Runtime
+*/
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub enum Status {
+    NotStarted,
+    Running,
+    Completed,
+    Cancelled,
+}
+
+impl Status {
+    /**
+        Returns `true` if the runtime is currently running.
+    */
+    #[must_use]
+    pub fn is_running(self) -> bool {
+        matches!(self, Status::Running)
+    }
+
+    /**
+        Returns `true` if the runtime has completed running.
+    */
+    #[must_use]
+    pub fn is_completed(self) -> bool {
+        matches!(self, Status::Completed)
+    }
+
+    /**
+        Returns `true` if the runtime was cancelled via [`Runtime::cancel`].
+
+        [`Runtime::cancel`]: crate::Runtime::cancel
+    */
+    #[must_use]
+    pub fn is_cancelled(self) -> bool {
+        matches!(self, Status::Cancelled)
+    }
+}
@@ -5,7 +5,7 @@ use std::{future::Future, rc::Weak as WeakRc, sync::Weak as WeakArc};
 
 use mlua::prelude::*;
 
-use async_executor::{Executor, Task};
+use async_executor::{Executor, StaticExecutor, Task};
 
 use crate::{
     handle::Handle,
@@ -199,6 +199,13 @@ impl<'lua> LuaRuntimeExt<'lua> for Lua {
     }
 
     fn spawn<T: Send + 'static>(&self, fut: impl Future<Output = T> + Send + 'static) -> Task<T> {
+        // Leaked runtimes (Runtime::run_leaked) store a 'static executor
+        // reference directly, skipping the Weak upgrade on every spawn
+        if let Some(exec) = self.app_data_ref::<&'static StaticExecutor>() {
+            tracing::trace!("spawning future on leaked executor");
+            return exec.spawn(fut);
+        }
+
         let exec = self
             .app_data_ref::<WeakArc<Executor>>()
             .expect("futures can only be spawned within a runtime")
@@ -209,6 +216,14 @@ impl<'lua> LuaRuntimeExt<'lua> for Lua {
     }
 
     fn spawn_local(&self, fut: impl Future<Output = ()> + 'static) {
+        // Leaked runtimes (Runtime::run_leaked) store a 'static futures
+        // queue reference directly, skipping the Weak upgrade on every spawn
+        if let Some(queue) = self.app_data_ref::<&'static FuturesQueue>() {
+            tracing::trace!("spawning local future on leaked executor");
+            queue.push_item(fut);
+            return;
+        }
+
         let queue = self
             .app_data_ref::<WeakRc<FuturesQueue>>()
             .expect("futures can only be spawned within a runtime")
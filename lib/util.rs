@@ -0,0 +1,61 @@
+use futures_lite::StreamExt;
+use mlua::prelude::*;
+use smol::channel::Sender;
+
+const ERR_OOM: &str = "out of memory";
+
+/**
+    Resumes the given thread, driving any internal async awaits to completion,
+    until it either yields a value back via `coroutine.yield` or finishes running.
+*/
+pub async fn run_until_yield<'lua>(
+    thread: LuaThread<'lua>,
+    args: LuaMultiValue<'lua>,
+) -> LuaResult<LuaMultiValue<'lua>> {
+    let mut stream = thread.into_async::<_, LuaMultiValue>(args);
+    match stream.next().await {
+        Some(result) => result,
+        None => Ok(LuaMultiValue::new()),
+    }
+}
+
+/**
+    Repeatedly resumes the given thread, forwarding every genuine
+    `coroutine.yield` over `tx` as it happens, until the thread
+    completes or errors, at which point `tx` is dropped and closes.
+
+    Because each resume is driven through [`run_until_yield`], which
+    drives internal async awaits (the runtime's own scheduling) to
+    completion via [`LuaThread::into_async`] before returning, only
+    values yielded by genuine `coroutine.yield` calls are ever observed
+    here - the runtime's own internal polling never surfaces as an item.
+*/
+pub async fn run_thread_streaming<'lua>(
+    lua: &'lua Lua,
+    thread: LuaThread<'lua>,
+    mut args: LuaMultiValue<'lua>,
+    tx: Sender<LuaResult<LuaRegistryKey>>,
+) {
+    loop {
+        let result = run_until_yield(thread.clone(), args).await;
+        let is_resumable = thread.status() == LuaThreadStatus::Resumable;
+
+        // A successful terminal completion (the thread fell off the end
+        // without erroring) is not a genuine `coroutine.yield` - close the
+        // channel without forwarding it as a stream item.
+        if !is_resumable && result.is_ok() {
+            break;
+        }
+
+        let forwarded = result.map(|values| {
+            lua.create_registry_value(values.into_vec())
+                .expect(ERR_OOM)
+        });
+
+        if tx.send(forwarded).await.is_err() || !is_resumable {
+            break;
+        }
+
+        args = LuaMultiValue::new();
+    }
+}